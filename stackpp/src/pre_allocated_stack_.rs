@@ -36,9 +36,13 @@ pub struct PreAllocatedStack {
 impl Stack for PreAllocatedStack {
     fn new(total_size: usize) -> Result<Self, Error> {
         unsafe {
+            let page = page_size();
+            // Round up to a whole number of pages: callers may ask for any size, but every
+            // length handed to `alloc`/`extend_usable` must be a page multiple.
+            let total_size = (total_size + page - 1) / page * page;
             // Add 4 extra pages at the top of the stack if we use the whole size, so that there
             // is enough stack for the exception handler on windows to use if we reach the limit.
-            let total_size = total_size + 4 * page_size();
+            let total_size = total_size + 4 * page;
             let guard_top = Self::alloc(total_size)?;
             let bottom = guard_top.add(total_size);
             let top = Self::extend_usable(bottom, page_size())?;
@@ -82,6 +86,9 @@ impl Stack for PreAllocatedStack {
     }
 
     fn give_to_signal(self) {
+        #[cfg(target_family = "unix")]
+        altstack::ensure_installed();
+
         CURRENT_STACK.with(|stack| stack.set(Some(self)))
     }
 
@@ -116,9 +123,16 @@ impl Stack for PreAllocatedStack {
 
         CURRENT_STACK.with(|stack| {
             let si_addr = (*siginfo).si_addr;
+            // `CURRENT_STACK` is only `Some` while guest code is actually running inside a
+            // wormhole (between `give_to_signal`/`take_from_signal`); as the process-wide
+            // handler installed by `install_handler`, this fires for unrelated faults on any
+            // thread too, which is the common case. Treat "no stack" the same as "not a guard
+            // page access we can grow": fall through so the caller chains to whatever handled
+            // this signal before us, instead of panicking across a signal frame (unwinding
+            // through one is UB).
             let mut stack = match stack.take() {
                 Some(stack) => stack,
-                None => panic!("Stack's signal handler can't find a stack"),
+                None => return false,
             };
             if stack.stack_pointer_inside_guard(si_addr as *mut u8) {
                 let result = stack.grow();
@@ -132,41 +146,125 @@ impl Stack for PreAllocatedStack {
         })
     }
     #[cfg(target_family = "windows")]
-    unsafe extern "system" fn signal_handler(_exception_info: winapi::um::winnt::PEXCEPTION_POINTERS) -> bool {
-        false // No op on windows
-
-        // use winapi::um::minwinbase::EXCEPTION_GUARD_PAGE;
-
-        // let record = &*(*exception_info).ExceptionRecord;
-        // if record.ExceptionCode != EXCEPTION_GUARD_PAGE {
-        //     return false;
-        // }
-
-        // CURRENT_STACK.with(|stack| {
-        //     // The second element of ExceptionInformation contains the address of the violation
-        //     let si_addr = record.ExceptionInformation[1];
-        //     let mut stack = match stack.take() {
-        //         Some(stack) => stack,
-        //         None => panic!("Stack's signal handler can't find a stack"),
-        //     };
-        //     if stack.stack_pointer_inside_guard(si_addr as *mut u8) {
-        //         let result = stack.grow();
-        //         if result.is_ok() {
-        //             stack.give_to_signal();
-        //             return true;
-        //         }
-        //     }
-        //     stack.give_to_signal();
-        //     return false;
-        // })
+    unsafe extern "system" fn signal_handler(exception_info: winapi::um::winnt::PEXCEPTION_POINTERS) -> bool {
+        use winapi::um::minwinbase::EXCEPTION_GUARD_PAGE;
+        use winapi::um::winnt::EXCEPTION_ACCESS_VIOLATION;
+
+        // `ExceptionCode` is a `DWORD`; `ntstatus::STATUS_STACK_OVERFLOW` is a signed `NTSTATUS`
+        // and can't be matched against it directly, so (like libstd's own Windows stack-overflow
+        // handler) we redefine it here with the right type.
+        const EXCEPTION_STACK_OVERFLOW: winapi::shared::minwindef::DWORD = 0xc00000fd;
+
+        let record = &*(*exception_info).ExceptionRecord;
+        // Guard page hits show up as EXCEPTION_GUARD_PAGE, but some Windows versions raise
+        // EXCEPTION_ACCESS_VIOLATION or EXCEPTION_STACK_OVERFLOW for the same condition, so we
+        // treat all three as growth candidates and let `stack_pointer_inside_guard` be the
+        // final arbiter of whether the fault actually landed on our guard page.
+        match record.ExceptionCode {
+            EXCEPTION_GUARD_PAGE | EXCEPTION_ACCESS_VIOLATION | EXCEPTION_STACK_OVERFLOW => {}
+            _ => return false,
+        }
+
+        CURRENT_STACK.with(|stack| {
+            // The second element of ExceptionInformation contains the address of the violation
+            let si_addr = record.ExceptionInformation[1];
+            // As a process-wide vectored handler this also sees faults on threads/moments with
+            // no active wormhole; chain to whatever other handler is installed (e.g. Wasmtime's)
+            // instead of panicking across an exception frame, which would abort the process.
+            let mut stack = match stack.take() {
+                Some(stack) => stack,
+                None => return false,
+            };
+            if stack.stack_pointer_inside_guard(si_addr as *mut u8) {
+                let result = stack.grow();
+                if result.is_ok() {
+                    stack.give_to_signal();
+                    return true;
+                }
+            }
+            stack.give_to_signal();
+            return false;
+        })
     }
 
 }
 
+impl PreAllocatedStack {
+    /// Cooperative alternative to fault-driven guard-page growth, modeled on `stacker`'s
+    /// instrumentation: at a call site the caller controls, check how much usable stack remains
+    /// below the current stack pointer and, if it's under `red_zone` bytes, commit
+    /// `grow_by.max(red_zone)` more (rounded up to whole pages) before running `f`. The commit is
+    /// capped at what is left of the reserved guarded area, and if `extend_usable` fails nothing
+    /// is committed; `f` runs in either case. This lets deeply recursive guest code guarantee
+    /// headroom without ever taking a fault, and works even where `signal_handler` is a no-op
+    /// (no signals, or a sandbox that won't let the crate install one).
+    pub fn maybe_grow<R>(&mut self, red_zone: usize, grow_by: usize, f: impl FnOnce() -> R) -> R {
+        self.grow_if_needed(red_zone, grow_by);
+        f()
+    }
+
+    /// The grow-check step of `maybe_grow`, split out so `maybe_grow_current` can run it while
+    /// the stack is still owned locally and only hand it back to `CURRENT_STACK` afterwards.
+    fn grow_if_needed(&mut self, red_zone: usize, grow_by: usize) {
+        let probe = 0u8;
+        let sp = &probe as *const u8 as *mut u8;
+        let headroom = unsafe { sp.sub(self.top as usize) as usize };
+
+        if headroom < red_zone {
+            let page = page_size();
+            let wanted = grow_by.max(red_zone);
+            let wanted = (wanted + page - 1) / page * page;
+            let guarded = unsafe { self.top.sub(self.guard_top as usize) as usize };
+            let wanted = wanted.min(guarded);
+
+            if wanted > 0 {
+                if let Ok(new_top) = unsafe { Self::extend_usable(self.top, wanted) } {
+                    self.top = new_top;
+                }
+            }
+        }
+    }
+
+    /// The `Yielder`-facing entry point for `maybe_grow`: guest code running inside a wormhole
+    /// doesn't hold a `&mut PreAllocatedStack` (the stack it's running on lives in
+    /// `CURRENT_STACK`, same as it does for `signal_handler`), so this pulls it out to run the
+    /// grow check. Crucially, the stack is parked back into `CURRENT_STACK` *before* `f` runs, not
+    /// after: `f` is exactly the deeply-recursive guest code this exists to protect, and while it
+    /// runs the fault-driven `signal_handler` still needs to find a stack to grow if the
+    /// cooperative check under-grew or `extend_usable` failed. Panics if called other than from
+    /// inside an `AsyncWormhole`, where `CURRENT_STACK` is always populated.
+    pub fn maybe_grow_current<R>(red_zone: usize, grow_by: usize, f: impl FnOnce() -> R) -> R {
+        let mut stack = CURRENT_STACK
+            .with(|cell| cell.take())
+            .expect("maybe_grow_current called outside of a running AsyncWormhole");
+
+        stack.grow_if_needed(red_zone, grow_by);
+        CURRENT_STACK.with(|cell| cell.set(Some(stack)));
+
+        f()
+    }
+}
+
 #[cfg(target_family = "unix")]
-impl PreAllocatedStack { 
+impl PreAllocatedStack {
+    /// Installs `signal_handler` as the process-wide `SIGSEGV`/`SIGBUS` handler, so a plain
+    /// `AsyncWormhole` gets automatic stack growth without anything external (e.g. Wasmtime's
+    /// `set_signal_handler`) wiring it in. Safe to call more than once or from multiple threads;
+    /// only the first call installs anything. Any handler already registered for these signals
+    /// (including `SIG_DFL`/`SIG_IGN`) is preserved and chained to whenever a fault isn't ours to
+    /// grow, so this coexists with other runtimes' handlers instead of replacing them.
+    pub fn install_handler() {
+        static INSTALL_HANDLER: std::sync::Once = std::sync::Once::new();
+        INSTALL_HANDLER.call_once(|| unsafe {
+            signal_chain::install(libc::SIGSEGV);
+            signal_chain::install(libc::SIGBUS);
+        });
+    }
+
     unsafe fn alloc(size: usize) -> Result<*mut u8, Error> {
-        use libc::{mmap, MAP_ANON, MAP_FAILED, MAP_NORESERVE, MAP_PRIVATE, PROT_NONE}; 
+        use libc::{mmap, MAP_ANON, MAP_FAILED, MAP_NORESERVE, MAP_PRIVATE, PROT_NONE};
+
+        debug_assert_eq!(size % page_size(), 0, "mmap size must be page-aligned");
 
         let ptr = mmap(
             ptr::null_mut(),
@@ -188,6 +286,13 @@ impl PreAllocatedStack {
     unsafe fn extend_usable(top: *mut u8, size: usize) -> Result<*mut u8, Error> {
         use libc::{mprotect, PROT_READ, PROT_WRITE};
 
+        debug_assert_eq!(size % page_size(), 0, "mprotect size must be page-aligned");
+        debug_assert_eq!(
+            top as usize % page_size(),
+            0,
+            "mprotect address must be page-aligned"
+        );
+
         if mprotect(
             top.sub(size) as *mut libc::c_void,
             size,
@@ -207,6 +312,8 @@ impl PreAllocatedStack {
         use winapi::um::memoryapi::VirtualAlloc;
         use winapi::um::winnt::{MEM_RESERVE, PAGE_GUARD, PAGE_READWRITE};
 
+        debug_assert_eq!(size % page_size(), 0, "VirtualAlloc size must be page-aligned");
+
         let ptr = VirtualAlloc(ptr::null_mut(), size, MEM_RESERVE, PAGE_GUARD | PAGE_READWRITE);
         if ptr.is_null() {
             Err(Error::last_os_error())
@@ -219,6 +326,13 @@ impl PreAllocatedStack {
         use winapi::um::memoryapi::VirtualAlloc;
         use winapi::um::winnt::{MEM_COMMIT, PAGE_READWRITE, PAGE_GUARD};
 
+        debug_assert_eq!(size % page_size(), 0, "VirtualAlloc size must be page-aligned");
+        debug_assert_eq!(
+            top as usize % page_size(),
+            0,
+            "VirtualAlloc address must be page-aligned"
+        );
+
         if !VirtualAlloc(
             top.sub(size) as *mut winapi::ctypes::c_void,
             size,
@@ -287,13 +401,227 @@ pub fn page_size() -> usize {
     static PAGE_SIZE_CACHE: AtomicUsize = AtomicUsize::new(0);
     match PAGE_SIZE_CACHE.load(Ordering::Relaxed) {
         0 => {
-            // Assure that we are using 4KB pages on all platforms.
+            // No assumption about the value: it's 4 KB on most x86/ARM Linux and Windows
+            // systems, but 16 KB on Apple Silicon and can be configured to 64 KB on some Linux
+            // kernels. Everything downstream (`Stack::new`'s extra pages, the initial
+            // `extend_usable`, the Windows top guard page) is expressed in multiples of this
+            // value instead of a hard-coded size.
             let page_size = sys_page_size();
-            assert_eq!(page_size, 4096);
 
             PAGE_SIZE_CACHE.store(page_size, Ordering::Relaxed);
             page_size
         }
         page_size => page_size,
     }
+}
+
+/// Runs the stack-growth `signal_handler` on its own alternate signal stack.
+///
+/// A guard-page fault means the thread's normal stack is exhausted, so the handler can't safely
+/// run on it: pushing even one more frame there would fault again and abort the process instead
+/// of growing the stack. This mirrors libstd's `sys::unix::stack_overflow`: each OS thread that
+/// hands a stack to the signal handler gets its own `mmap`ed alternate stack, registered with
+/// `sigaltstack(2)`, with a `PROT_NONE` guard page below it so an overflow of the alt-stack
+/// itself is a clean fault rather than silent corruption of whatever memory follows it.
+#[cfg(target_family = "unix")]
+mod altstack {
+    use std::cell::RefCell;
+    use std::io::Error;
+    use std::ptr;
+
+    use libc::{
+        mmap, mprotect, munmap, sigaltstack, stack_t, MAP_ANON, MAP_FAILED, MAP_PRIVATE,
+        PROT_NONE, PROT_READ, PROT_WRITE, SS_DISABLE,
+    };
+
+    use super::page_size;
+
+    thread_local! {
+        static ALT_STACK: RefCell<Option<AltStackGuard>> = RefCell::new(None);
+    }
+
+    /// Owns the alternate signal stack's `mmap`ed region for the lifetime of the thread. Disables
+    /// the alt-stack and unmaps the region on drop.
+    struct AltStackGuard {
+        mapping: *mut u8,
+        mapping_size: usize,
+    }
+
+    impl Drop for AltStackGuard {
+        fn drop(&mut self) {
+            unsafe {
+                let disable = stack_t {
+                    ss_sp: ptr::null_mut(),
+                    ss_flags: SS_DISABLE,
+                    ss_size: 0,
+                };
+                sigaltstack(&disable, ptr::null_mut());
+                munmap(self.mapping as *mut libc::c_void, self.mapping_size);
+            }
+        }
+    }
+
+    fn round_up_to_page(size: usize) -> usize {
+        let page = page_size();
+        (size + page - 1) / page * page
+    }
+
+    /// The minimum alternate signal stack size. There's no portable libc constant for a
+    /// runtime-queried minimum (`_SC_SIGSTKSZ` isn't defined for glibc Linux in the `libc` crate),
+    /// so we just use the compile-time `SIGSTKSZ` constant, same as everywhere else.
+    fn min_alt_stack_size() -> usize {
+        libc::SIGSTKSZ
+    }
+
+    /// Installs an alternate signal stack for the current thread, if one isn't already installed.
+    pub(crate) fn ensure_installed() {
+        ALT_STACK.with(|slot| {
+            if slot.borrow().is_some() {
+                return;
+            }
+
+            let page = page_size();
+            let alt_stack_size = round_up_to_page(min_alt_stack_size());
+            let mapping_size = page + alt_stack_size;
+
+            unsafe {
+                let mapping = mmap(
+                    ptr::null_mut(),
+                    mapping_size,
+                    PROT_NONE,
+                    MAP_PRIVATE | MAP_ANON,
+                    -1,
+                    0,
+                );
+                if mapping == MAP_FAILED {
+                    panic!(
+                        "failed to mmap alternate signal stack: {}",
+                        Error::last_os_error()
+                    );
+                }
+
+                // The first page stays PROT_NONE as a guard; the alt-stack itself starts right
+                // after it and grows down towards it.
+                let alt_stack_base = (mapping as *mut u8).add(page);
+                if mprotect(
+                    alt_stack_base as *mut libc::c_void,
+                    alt_stack_size,
+                    PROT_READ | PROT_WRITE,
+                ) != 0
+                {
+                    panic!(
+                        "failed to mprotect alternate signal stack: {}",
+                        Error::last_os_error()
+                    );
+                }
+
+                let stack = stack_t {
+                    ss_sp: alt_stack_base as *mut libc::c_void,
+                    ss_flags: 0,
+                    ss_size: alt_stack_size,
+                };
+                if sigaltstack(&stack, ptr::null_mut()) != 0 {
+                    panic!("failed to sigaltstack: {}", Error::last_os_error());
+                }
+
+                *slot.borrow_mut() = Some(AltStackGuard {
+                    mapping: mapping as *mut u8,
+                    mapping_size,
+                });
+            }
+        });
+    }
+}
+
+/// Installs `PreAllocatedStack::signal_handler` for a signal and remembers whatever was
+/// registered before it, so a fault this crate doesn't want to grow from still reaches whoever
+/// was handling the signal beforehand (another runtime, or the process default).
+///
+/// The previous disposition is stashed in atomics rather than behind a lock: signal handlers must
+/// not block, and `sigaction`'s old-disposition output can't be produced ahead of time, so there's
+/// no way to fill a `Once`-guarded slot before the handler can possibly run.
+#[cfg(target_family = "unix")]
+mod signal_chain {
+    use std::mem;
+    use std::ptr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::PreAllocatedStack;
+
+    // Indexed by `index_for`. `AtomicUsize` rather than `static mut`, following libstd's
+    // stack-overflow handler: these are written once from `install` and read from signal context,
+    // where a data race on a plain static would be undefined behavior.
+    static PREV_HANDLER: [AtomicUsize; 2] = [AtomicUsize::new(0), AtomicUsize::new(0)];
+    static PREV_FLAGS: [AtomicUsize; 2] = [AtomicUsize::new(0), AtomicUsize::new(0)];
+
+    fn index_for(signum: libc::c_int) -> usize {
+        match signum {
+            libc::SIGSEGV => 0,
+            libc::SIGBUS => 1,
+            _ => unreachable!("signal_chain only installs SIGSEGV and SIGBUS"),
+        }
+    }
+
+    /// Registers `trampoline` for `signum`, saving the previously installed action.
+    pub(crate) unsafe fn install(signum: libc::c_int) {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_flags = libc::SA_SIGINFO | libc::SA_ONSTACK;
+        action.sa_sigaction = trampoline as usize;
+        libc::sigemptyset(&mut action.sa_mask);
+
+        let mut prev: libc::sigaction = mem::zeroed();
+        if libc::sigaction(signum, &action, &mut prev) != 0 {
+            panic!(
+                "failed to install signal handler for signal {}: {}",
+                signum,
+                std::io::Error::last_os_error()
+            );
+        }
+
+        let index = index_for(signum);
+        PREV_HANDLER[index].store(prev.sa_sigaction, Ordering::SeqCst);
+        PREV_FLAGS[index].store(prev.sa_flags as usize, Ordering::SeqCst);
+    }
+
+    /// Forwards a signal we decided not to grow from to whatever was handling it before us.
+    unsafe fn call_previous(
+        signum: libc::c_int,
+        siginfo: *mut libc::siginfo_t,
+        context: *mut libc::c_void,
+    ) {
+        let index = index_for(signum);
+        let handler = PREV_HANDLER[index].load(Ordering::SeqCst);
+        let flags = PREV_FLAGS[index].load(Ordering::SeqCst);
+
+        if handler == libc::SIG_DFL || handler == libc::SIG_IGN {
+            // There's nothing to call into: restore the original disposition and re-raise so the
+            // kernel's default action (typically terminating with a core dump) applies exactly as
+            // it would have without us in the picture.
+            let mut default: libc::sigaction = mem::zeroed();
+            default.sa_sigaction = handler;
+            libc::sigemptyset(&mut default.sa_mask);
+            libc::sigaction(signum, &default, ptr::null_mut());
+            libc::raise(signum);
+            return;
+        }
+
+        if flags & (libc::SA_SIGINFO as usize) != 0 {
+            let handler: extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void) =
+                mem::transmute(handler);
+            handler(signum, siginfo, context);
+        } else {
+            let handler: extern "C" fn(libc::c_int) = mem::transmute(handler);
+            handler(signum);
+        }
+    }
+
+    unsafe extern "C" fn trampoline(
+        signum: libc::c_int,
+        siginfo: *mut libc::siginfo_t,
+        context: *mut libc::c_void,
+    ) {
+        if !PreAllocatedStack::signal_handler(signum, siginfo, context) {
+            call_previous(signum, siginfo, context);
+        }
+    }
 }
\ No newline at end of file